@@ -35,6 +35,8 @@ async fn main() -> anyhow::Result<()> {
 
         test_gateway_registration(&dev_fed).await?;
         test_payments(&dev_fed).await?;
+        test_ptlc_payments(&dev_fed).await?;
+        test_hold_invoice_receive(&dev_fed).await?;
 
         Ok(())
     })
@@ -277,6 +279,91 @@ async fn test_payments(dev_fed: &DevJitFed) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn test_ptlc_payments(dev_fed: &DevJitFed) -> anyhow::Result<()> {
+    let federation = dev_fed.fed().await?;
+
+    let client = federation
+        .new_joined_client("lnv2-test-ptlc-payments-client")
+        .await?;
+
+    federation.pegin_client(10_000, &client).await?;
+
+    let gw_lnd = dev_fed.gw_lnd().await?;
+    let gw_ldk = dev_fed
+        .gw_ldk()
+        .await?
+        .as_ref()
+        .expect("Gateways of version 0.5.0 or higher support LDK");
+
+    federation
+        .pegin_gateways(1_000_000, vec![gw_lnd, gw_ldk])
+        .await?;
+
+    info!("Testing circular PTLC payments...");
+
+    for (gw_send, gw_receive) in [(gw_lnd, gw_ldk), (gw_ldk, gw_lnd)] {
+        info!(
+            "Testing PTLC payment: client -> {} -> {} -> client",
+            gw_send.ln_type(),
+            gw_receive.ln_type()
+        );
+
+        let (invoice, receive_op) = receive_ptlc(&client, &gw_receive.addr, 1_000_000).await?;
+
+        test_send_ptlc(
+            &client,
+            &gw_send.addr,
+            &invoice,
+            FinalSendOperationState::Success,
+        )
+        .await?;
+
+        await_receive_claimed(&client, receive_op).await?;
+    }
+
+    Ok(())
+}
+
+async fn test_hold_invoice_receive(dev_fed: &DevJitFed) -> anyhow::Result<()> {
+    let federation = dev_fed.fed().await?;
+
+    let client = federation
+        .new_joined_client("lnv2-test-hold-invoice-client")
+        .await?;
+
+    let gw_ldk = dev_fed
+        .gw_ldk()
+        .await?
+        .as_ref()
+        .expect("Gateways of version 0.5.0 or higher support LDK");
+
+    info!("Testing client-issued HOLD invoice is settled on demand...");
+
+    let (invoice, receive_op, preimage) = receive_hold(&client, &gw_ldk.addr, 1_000_000).await?;
+
+    // The incoming payment locks pending our decision; settling releases it.
+    try_join!(
+        gw_ldk.pay_invoice(invoice),
+        settle_hold(&client, receive_op, &preimage),
+    )?;
+
+    await_receive_claimed(&client, receive_op).await?;
+
+    info!("Testing client-issued HOLD invoice is cancelled on demand...");
+
+    let (invoice, receive_op, _preimage) = receive_hold(&client, &gw_ldk.addr, 1_000_000).await?;
+
+    cancel_hold(&client, receive_op).await?;
+
+    // Cancelling the hold refunds the sender rather than issuing ecash.
+    gw_ldk
+        .pay_invoice(invoice)
+        .await
+        .expect_err("Payment to a cancelled HOLD invoice must fail");
+
+    Ok(())
+}
+
 async fn add_gateway(client: &Client, peer: usize, gateway: &String) -> anyhow::Result<bool> {
     cmd!(
         client,
@@ -335,6 +422,115 @@ async fn receive(
     )?)
 }
 
+async fn receive_ptlc(
+    client: &Client,
+    gateway: &str,
+    amount: u64,
+) -> anyhow::Result<(Bolt11Invoice, OperationId)> {
+    Ok(serde_json::from_value::<(Bolt11Invoice, OperationId)>(
+        cmd!(
+            client,
+            "module",
+            "lnv2",
+            "receive",
+            amount,
+            "--gateway",
+            gateway,
+            "--contract",
+            "ptlc"
+        )
+        .out_json()
+        .await?,
+    )?)
+}
+
+async fn test_send_ptlc(
+    client: &Client,
+    gateway: &String,
+    invoice: &Bolt11Invoice,
+    final_state: FinalSendOperationState,
+) -> anyhow::Result<()> {
+    let send_op = serde_json::from_value::<OperationId>(
+        cmd!(
+            client,
+            "module",
+            "lnv2",
+            "send",
+            invoice.to_string(),
+            "--gateway",
+            gateway,
+            "--contract",
+            "ptlc"
+        )
+        .out_json()
+        .await?,
+    )?;
+
+    assert_eq!(
+        cmd!(
+            client,
+            "module",
+            "lnv2",
+            "await-send",
+            serde_json::to_string(&send_op)?.substring(1, 65)
+        )
+        .out_json()
+        .await?,
+        serde_json::to_value(final_state).expect("JSON serialization failed"),
+    );
+
+    Ok(())
+}
+
+async fn receive_hold(
+    client: &Client,
+    gateway: &str,
+    amount: u64,
+) -> anyhow::Result<(Bolt11Invoice, OperationId, String)> {
+    Ok(serde_json::from_value::<(Bolt11Invoice, OperationId, String)>(
+        cmd!(
+            client,
+            "module",
+            "lnv2",
+            "receive-hold",
+            amount,
+            "--gateway",
+            gateway
+        )
+        .out_json()
+        .await?,
+    )?)
+}
+
+async fn settle_hold(
+    client: &Client,
+    operation_id: OperationId,
+    preimage: &str,
+) -> anyhow::Result<()> {
+    cmd!(
+        client,
+        "module",
+        "lnv2",
+        "settle-hold",
+        serde_json::to_string(&operation_id)?.substring(1, 65),
+        preimage
+    )
+    .run()
+    .await
+}
+
+async fn cancel_hold(client: &Client, operation_id: OperationId) -> anyhow::Result<()> {
+    cmd!(
+        client,
+        "module",
+        "lnv2",
+        "cancel-hold",
+        serde_json::to_string(&operation_id)?.substring(1, 65)
+    )
+    .run()
+    .await
+}
+
 async fn test_send(
     client: &Client,
     gateway: &String,
@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+use bitcoin::psbt::Psbt;
 use bitcoin::{Address, Amount};
 use fedimint_api_client::api::{FederationApiExt, FederationResult, IModuleFederationApi};
 use fedimint_core::envs::BitcoinRpcConfig;
@@ -8,9 +10,11 @@ use fedimint_core::task::{MaybeSend, MaybeSync};
 use fedimint_core::{apply, async_trait_maybe_send, PeerId};
 use fedimint_wallet_common::endpoint_constants::{
     BITCOIN_KIND_ENDPOINT, BITCOIN_RPC_CONFIG_ENDPOINT, BLOCK_COUNT_ENDPOINT,
-    MODULE_CONSENSUS_VERSION_ENDPOINT, PEG_OUT_FEES_ENDPOINT, WALLET_SUMMARY_ENDPOINT,
+    CONSOLIDATION_PAUSE_ENDPOINT, CONSOLIDATION_RESUME_ENDPOINT, FORCE_CONSOLIDATION_ENDPOINT,
+    MODULE_CONSENSUS_VERSION_ENDPOINT, PAYJOIN_RECEIVER_ENDPOINT, PEG_OUT_FEES_ENDPOINT,
+    PEG_OUT_FEES_TIERED_ENDPOINT, WALLET_SUMMARY_ENDPOINT,
 };
-use fedimint_wallet_common::{PegOutFees, WalletSummary};
+use fedimint_wallet_common::{ConfirmationTarget, PegOutFees, WalletSummary};
 
 #[apply(async_trait_maybe_send!)]
 pub trait WalletFederationApi {
@@ -24,11 +28,72 @@ pub trait WalletFederationApi {
         amount: Amount,
     ) -> FederationResult<Option<PegOutFees>>;
 
+    /// Fetch the consensus `PegOutFees` for every [`ConfirmationTarget`] tier
+    /// at once, letting a client trade confirmation speed against cost. The
+    /// guardians agree on a feerate per tier (a median of their individual
+    /// `estimatesmartfee`/mempool views), so the returned map is deterministic
+    /// across peers.
+    async fn fetch_peg_out_fees_tiered(
+        &self,
+        address: &Address,
+        amount: Amount,
+    ) -> FederationResult<BTreeMap<ConfirmationTarget, Option<PegOutFees>>>;
+
+    /// Fetch the consensus `PegOutFees` for a single [`ConfirmationTarget`]
+    /// tier, for clients that already know which speed/cost trade-off they
+    /// want to submit a peg-out against.
+    async fn fetch_peg_out_fees_for_target(
+        &self,
+        target: ConfirmationTarget,
+        address: &Address,
+        amount: Amount,
+    ) -> FederationResult<Option<PegOutFees>>;
+
+    /// Fetch the `PegOutFees` for an explicit `sat/vB` feerate rather than a
+    /// consensus tier. The feerate is validated against the module's configured
+    /// min/max band on the server, so power users can pin an exact feerate
+    /// without escaping the federation's safety limits.
+    async fn fetch_peg_out_fees_with_feerate(
+        &self,
+        sats_per_vbyte: u64,
+        address: &Address,
+        amount: Amount,
+    ) -> FederationResult<Option<PegOutFees>>;
+
+    /// Fetch the kind of Bitcoin backend a guardian is running. Besides the
+    /// full-node `bitcoind`/`electrum` kinds this may now report `esplora` for
+    /// guardians syncing against an Esplora HTTP endpoint instead of a local
+    /// full node.
     async fn fetch_bitcoin_rpc_kind(&self, peer_id: PeerId) -> FederationResult<String>;
 
+    /// Fetch the guardian's `BitcoinRpcConfig`, which for an Esplora-backed
+    /// guardian carries the `esplora` kind and its base URL.
     async fn fetch_bitcoin_rpc_config(&self, auth: ApiAuth) -> FederationResult<BitcoinRpcConfig>;
 
     async fn fetch_wallet_summary(&self) -> FederationResult<WalletSummary>;
+
+    /// Drive the BIP78 payjoin receiver handshake for a peg-in. Given the
+    /// sender's original PSBT paying a peg-in address, the federation
+    /// contributes one of its own UTXOs as an additional input and returns the
+    /// modified PSBT for the sender to re-sign. If the sender never returns the
+    /// signed payjoin the deposit still confirms as an ordinary peg-in.
+    async fn payjoin_peg_in(
+        &self,
+        address: &Address,
+        original_psbt: &Psbt,
+    ) -> FederationResult<Psbt>;
+
+    /// Force the federation to propose a UTXO consolidation transaction now,
+    /// regardless of the current feerate trigger. Intended for operators who
+    /// want to consolidate dust during a known low-fee window.
+    async fn force_consolidation(&self, auth: ApiAuth) -> FederationResult<()>;
+
+    /// Pause automatic UTXO consolidation until it is resumed. Useful to keep
+    /// the wallet's UTXO set stable during maintenance.
+    async fn pause_consolidation(&self, auth: ApiAuth) -> FederationResult<()>;
+
+    /// Resume automatic UTXO consolidation after it has been paused.
+    async fn resume_consolidation(&self, auth: ApiAuth) -> FederationResult<()>;
 }
 
 #[apply(async_trait_maybe_send!)]
@@ -75,6 +140,50 @@ where
         .await
     }
 
+    async fn fetch_peg_out_fees_tiered(
+        &self,
+        address: &Address,
+        amount: Amount,
+    ) -> FederationResult<BTreeMap<ConfirmationTarget, Option<PegOutFees>>> {
+        self.request_current_consensus(
+            PEG_OUT_FEES_TIERED_ENDPOINT.to_string(),
+            ApiRequestErased::new((address, amount.to_sat(), None::<u64>)),
+        )
+        .await
+    }
+
+    async fn fetch_peg_out_fees_for_target(
+        &self,
+        target: ConfirmationTarget,
+        address: &Address,
+        amount: Amount,
+    ) -> FederationResult<Option<PegOutFees>> {
+        Ok(self
+            .fetch_peg_out_fees_tiered(address, amount)
+            .await?
+            .remove(&target)
+            .flatten())
+    }
+
+    async fn fetch_peg_out_fees_with_feerate(
+        &self,
+        sats_per_vbyte: u64,
+        address: &Address,
+        amount: Amount,
+    ) -> FederationResult<Option<PegOutFees>> {
+        // The server re-keys an explicit feerate override under
+        // `ConfirmationTarget::Custom` after validating it against the
+        // configured min/max band, so we pull that entry back out here.
+        Ok(self
+            .request_current_consensus::<BTreeMap<ConfirmationTarget, Option<PegOutFees>>>(
+                PEG_OUT_FEES_TIERED_ENDPOINT.to_string(),
+                ApiRequestErased::new((address, amount.to_sat(), Some(sats_per_vbyte))),
+            )
+            .await?
+            .remove(&ConfirmationTarget::Custom(sats_per_vbyte))
+            .flatten())
+    }
+
     async fn fetch_bitcoin_rpc_kind(&self, peer_id: PeerId) -> FederationResult<String> {
         self.request_single_peer_federation(
             Some(Duration::from_secs(10)),
@@ -101,4 +210,43 @@ where
         )
         .await
     }
+
+    async fn payjoin_peg_in(
+        &self,
+        address: &Address,
+        original_psbt: &Psbt,
+    ) -> FederationResult<Psbt> {
+        self.request_current_consensus(
+            PAYJOIN_RECEIVER_ENDPOINT.to_string(),
+            ApiRequestErased::new((address, original_psbt)),
+        )
+        .await
+    }
+
+    async fn force_consolidation(&self, auth: ApiAuth) -> FederationResult<()> {
+        self.request_admin(
+            FORCE_CONSOLIDATION_ENDPOINT,
+            ApiRequestErased::default(),
+            auth,
+        )
+        .await
+    }
+
+    async fn pause_consolidation(&self, auth: ApiAuth) -> FederationResult<()> {
+        self.request_admin(
+            CONSOLIDATION_PAUSE_ENDPOINT,
+            ApiRequestErased::default(),
+            auth,
+        )
+        .await
+    }
+
+    async fn resume_consolidation(&self, auth: ApiAuth) -> FederationResult<()> {
+        self.request_admin(
+            CONSOLIDATION_RESUME_ENDPOINT,
+            ApiRequestErased::default(),
+            auth,
+        )
+        .await
+    }
 }